@@ -2,22 +2,105 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use anyhow::Result;
 use futures::StreamExt;
+use k8s_openapi::api::core::v1::{Namespace, Secret};
 use k8s_openapi::api::networking::v1::{Ingress, IngressSpec, IngressTLS};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
 use kube::{
-    api::{ListParams, ObjectMeta, Patch, PatchParams},
+    api::{DeleteParams, ListParams, ObjectMeta, Patch, PatchParams},
     runtime::controller::{Action, Context, Controller},
-    Api, Client, ResourceExt,
+    runtime::events::{Event, EventType, Recorder, Reporter},
+    runtime::finalizer::{finalizer, Event as FinalizerEvent},
+    runtime::reflector::ObjectRef,
+    Api, Client, CustomResource, ResourceExt,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tokio::time::Duration;
 use tracing::*;
 
+/// Finalizer we register on Ingresses we patch so we get a chance to revert
+/// our changes before the object is removed.
+const FINALIZER: &str = "autotls-controller/finalizer";
+
+/// Minimal view of the cert-manager `Certificate` CRD. We only model the
+/// fields we read so we can learn whether the certificate we implicitly
+/// requested was actually issued.
+#[derive(CustomResource, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[kube(
+    group = "cert-manager.io",
+    version = "v1",
+    kind = "Certificate",
+    namespaced,
+    status = "CertificateStatus"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateSpec {
+    pub secret_name: String,
+    #[serde(default)]
+    pub dns_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct CertificateStatus {
+    #[serde(default)]
+    pub conditions: Vec<CertificateCondition>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct CertificateCondition {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Reads the `Ready` condition of a managed Certificate and collapses it into
+/// the value we surface through the `autotls/status` annotation.
+fn certificate_status(cert: &Certificate) -> &'static str {
+    let ready = cert
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.iter().find(|c| c.type_ == "Ready"));
+    match ready {
+        Some(c) if c.status == "True" => "Issued",
+        Some(c) if c.status == "False" => "Failed",
+        _ => "Pending",
+    }
+}
+
+/// Maps a managed child object back to its parent Ingress.
+///
+/// We prefer an explicit owner reference to an Ingress (cert-manager's
+/// ingress-shim stamps one on the Certificates it creates), which is
+/// unambiguous regardless of the per-domain `{name}-{slug}-tls` naming scheme.
+/// We only fall back to the `{name}-tls` suffix convention for children that
+/// carry no such owner reference.
+fn parent_ingress<K: ResourceExt>(child: &K) -> Option<ObjectRef<Ingress>> {
+    let name = child
+        .owner_references()
+        .iter()
+        .find(|o| o.kind == "Ingress")
+        .map(|o| o.name.clone())
+        .or_else(|| child.name().strip_suffix("-tls").map(str::to_owned))?;
+    let mut oref = ObjectRef::<Ingress>::new(&name);
+    if let Some(ns) = child.namespace() {
+        oref = oref.within(&ns);
+    }
+    Some(oref)
+}
+
 #[derive(Debug, Error)]
 enum Error {
     #[error("Failed to patch Ingress: {0}")]
     IngressPatchFailed(#[source] kube::Error),
+    #[error("Failed to clean up Ingress: {0}")]
+    IngressCleanupFailed(#[source] kube::Error),
     #[error("MissingObjectKey: {0}")]
     MissingObjectKey(&'static str),
+    #[error("Finalizer error: {0}")]
+    FinalizerError(#[source] Box<kube::runtime::finalizer::Error<Error>>),
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
 }
@@ -38,18 +121,30 @@ fn patch_domain(generator: Arc<Ingress>, domain: &str) -> Result<Option<Ingress>
         return Ok(None);
     }
 
+    // `autotls/domain` may be a comma-separated list, in which case a dotless
+    // host expands into one rule per domain.
+    let domains = domain
+        .split(',')
+        .map(|d| d.trim())
+        .filter(|d| !d.is_empty())
+        .collect::<Vec<_>>();
+
     let mut patched = false;
     let rules = spec.rules.as_ref().map(|r| {
         r.iter()
-            .map(|ir| {
-                let mut ir = ir.clone();
-                if let Some(host) = & mut ir.host {
-                    if !host.contains(".") {
-                        *host = format!("{host}.{domain}");
-                        patched = true;
-                    }
+            .flat_map(|ir| match &ir.host {
+                Some(host) if !host.contains(".") => {
+                    patched = true;
+                    domains
+                        .iter()
+                        .map(|d| {
+                            let mut ir = ir.clone();
+                            ir.host = Some(format!("{host}.{d}"));
+                            ir
+                        })
+                        .collect::<Vec<_>>()
                 }
-                ir
+                _ => vec![ir.clone()],
             })
             .collect::<Vec<_>>()
     });
@@ -72,6 +167,36 @@ fn patch_domain(generator: Arc<Ingress>, domain: &str) -> Result<Option<Ingress>
     Ok(Some(ingress))
 }
 
+/// The parent domain of a host, i.e. everything after the first label when the
+/// host has a subdomain. `foo.example.com` -> `example.com`, while an apex host
+/// like `example.com` (or a dotless one) is its own parent, so we never derive
+/// a single-label wildcard such as `*.com`. Multi-label public suffixes are not
+/// recognised: `foo.co.uk` -> `co.uk`.
+fn parent_domain(host: &str) -> &str {
+    if host.matches('.').count() >= 2 {
+        host.split_once('.').map(|(_, d)| d).unwrap_or(host)
+    } else {
+        host
+    }
+}
+
+/// The secret names we manage for an Ingress, read straight from its own TLS
+/// stanza so status and cleanup target exactly the secrets we requested.
+fn managed_secret_names(ingress: &Ingress) -> Vec<String> {
+    ingress
+        .spec
+        .as_ref()
+        .and_then(|s| s.tls.as_ref())
+        .map(|tls| tls.iter().filter_map(|t| t.secret_name.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// Turns a domain into a secret-name-safe slug so each domain can own a
+/// deterministic certificate secret.
+fn domain_slug(domain: &str) -> String {
+    domain.replace('.', "-")
+}
+
 fn patch_tls(generator: Arc<Ingress>, issuer: &str) -> Result<Option<Ingress>> {
     use anyhow::Context;
 
@@ -98,17 +223,102 @@ fn patch_tls(generator: Arc<Ingress>, issuer: &str) -> Result<Option<Ingress>> {
         .filter_map(|r| r.host.as_ref().map(|s| s.clone()))
         .collect::<Vec<_>>();
 
+    // The forced HTTPS redirect is on by default but can be disabled with
+    // `autotls/ssl-redirect: false` for edges that terminate elsewhere.
+    let ssl_redirect = match generator
+        .annotations()
+        .get("autotls/ssl-redirect")
+        .map(String::as_str)
+    {
+        None | Some("true") => true,
+        Some("false") => false,
+        Some(other) => {
+            warn!("Ingress {name} has invalid autotls/ssl-redirect {other:?}, skipping");
+            return Ok(None);
+        }
+    };
+
+    // Opting into `autotls/backend-protocol: HTTPS` emits the
+    // `ingress.kubernetes.io/backend-protocol: HTTPS` annotation so the whole
+    // path is TLS, not just the edge. Backend service ports are not changed.
+    let backend_protocol = match generator
+        .annotations()
+        .get("autotls/backend-protocol")
+        .map(String::as_str)
+    {
+        None | Some("HTTP") => None,
+        Some("HTTPS") => Some("HTTPS"),
+        Some(other) => {
+            warn!("Ingress {name} has invalid autotls/backend-protocol {other:?}, skipping");
+            return Ok(None);
+        }
+    };
+
     let mut annotations = BTreeMap::<String, String>::new();
     annotations.insert(
         "ingress.kubernetes.io/ssl-redirect".to_owned(),
-        "true".to_owned(),
+        ssl_redirect.to_string(),
     );
+    if let Some(protocol) = backend_protocol {
+        annotations.insert(
+            "ingress.kubernetes.io/backend-protocol".to_owned(),
+            protocol.to_owned(),
+        );
+    }
     if issuer == "auto" {
         annotations.insert("kubernetes.io/tls-acme".to_owned(), "true".to_owned());
     } else {
         annotations.insert("cert-manager.io/cluster-issuer".to_owned(), issuer.to_owned());
     }
 
+    // Group hosts by their parent domain so each domain gets its own TLS
+    // entry, enabling per-domain and wildcard certificates. Outside wildcard
+    // mode a single group keeps the original `{name}-tls` secret for backwards
+    // compatibility; wildcard mode always uses `{name}-{slug}-tls`.
+    let wildcard = generator
+        .annotations()
+        .get("autotls/wildcard")
+        .map(String::as_str)
+        == Some("true");
+
+    let mut groups = BTreeMap::<String, Vec<String>>::new();
+    for host in hosts {
+        groups
+            .entry(parent_domain(&host).to_owned())
+            .or_default()
+            .push(host);
+    }
+    let single = groups.len() == 1 && !wildcard;
+
+    let tls = groups
+        .into_iter()
+        .map(|(domain, mut hosts)| {
+            let secret_name = if single {
+                format!("{name}-tls")
+            } else {
+                format!("{name}-{}-tls", domain_slug(&domain))
+            };
+            let hosts = if wildcard {
+                // Collapse all same-domain hosts into a single wildcard SAN,
+                // keeping an apex host (equal to the domain) alongside it since
+                // `*.domain` does not cover the bare `domain`.
+                let mut collapsed = vec![format!("*.{domain}")];
+                if hosts.iter().any(|h| h == &domain) {
+                    collapsed.push(domain);
+                }
+                collapsed
+            } else {
+                hosts.sort();
+                hosts.dedup();
+                hosts
+            };
+            IngressTLS {
+                hosts: Some(hosts),
+                secret_name: Some(secret_name),
+            }
+        })
+        .collect::<Vec<_>>();
+
     let ingress = Ingress {
         metadata: ObjectMeta {
             name: generator.metadata.name.clone(),
@@ -116,10 +326,7 @@ fn patch_tls(generator: Arc<Ingress>, issuer: &str) -> Result<Option<Ingress>> {
             ..ObjectMeta::default()
         },
         spec: Some(IngressSpec {
-            tls: Some(vec![IngressTLS {
-                hosts: Some(hosts),
-                secret_name: Some(format!("{name}-tls")),
-            }]),
+            tls: Some(tls),
             ..Default::default()
         }),
         ..Default::default()
@@ -127,6 +334,150 @@ fn patch_tls(generator: Arc<Ingress>, issuer: &str) -> Result<Option<Ingress>> {
     Ok(Some(ingress))
 }
 
+/// Returns whether we own this Ingress given the configured ingress class.
+///
+/// When no class is configured we reconcile every Ingress, preserving the
+/// original cluster-wide behavior. Otherwise we match either the canonical
+/// `spec.ingressClassName` field or the legacy `kubernetes.io/ingress.class`
+/// annotation, the same pair Traefik's Kubernetes provider checks.
+fn ingress_class_matches(generator: &Ingress, ingress_class: &Option<String>) -> bool {
+    let wanted = match ingress_class {
+        Some(w) => w,
+        None => return true,
+    };
+
+    let actual = generator
+        .spec
+        .as_ref()
+        .and_then(|s| s.ingress_class_name.clone())
+        .or_else(|| {
+            generator
+                .annotations()
+                .get("kubernetes.io/ingress.class")
+                .cloned()
+        });
+
+    actual.as_deref() == Some(wanted.as_str())
+}
+
+/// Cluster-wide autotls configuration that targets Ingresses by selector,
+/// letting a platform team enable autotls for whole namespaces without
+/// annotating individual Ingress objects. Explicit annotations still win.
+#[derive(CustomResource, Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[kube(group = "autotls.agorgl.github.io", version = "v1", kind = "AutoTLSPolicy")]
+#[serde(rename_all = "camelCase")]
+pub struct AutoTLSPolicySpec {
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Matches the labels of candidate Ingresses. Empty matches all.
+    #[serde(default)]
+    pub ingress_selector: Option<LabelSelector>,
+    /// Matches the labels of the Ingress' namespace. Empty matches all.
+    #[serde(default)]
+    pub namespace_selector: Option<LabelSelector>,
+}
+
+/// The effective domain/issuer for an Ingress after merging annotations (which
+/// take precedence) with any matching [`AutoTLSPolicy`].
+#[derive(Debug, Default)]
+struct Settings {
+    domain: Option<String>,
+    issuer: Option<String>,
+}
+
+/// Evaluates a Kubernetes [`LabelSelector`] against a set of labels. A missing
+/// selector matches everything, mirroring apimachinery's semantics.
+fn selector_matches(selector: &Option<LabelSelector>, labels: &BTreeMap<String, String>) -> bool {
+    let selector = match selector {
+        Some(s) => s,
+        None => return true,
+    };
+
+    if let Some(match_labels) = &selector.match_labels {
+        for (k, v) in match_labels {
+            if labels.get(k) != Some(v) {
+                return false;
+            }
+        }
+    }
+
+    if let Some(exprs) = &selector.match_expressions {
+        for expr in exprs {
+            let present = labels.get(&expr.key);
+            let matched = match expr.operator.as_str() {
+                "In" => present
+                    .map(|v| expr.values.as_ref().map_or(false, |vs| vs.contains(v)))
+                    .unwrap_or(false),
+                "NotIn" => present
+                    .map(|v| expr.values.as_ref().map_or(true, |vs| !vs.contains(v)))
+                    .unwrap_or(true),
+                "Exists" => present.is_some(),
+                "DoesNotExist" => present.is_none(),
+                _ => false,
+            };
+            if !matched {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Resolves the effective settings for an Ingress: explicit annotations first,
+/// falling back to a matching [`AutoTLSPolicy`]. `AutoTLSPolicy` is a
+/// cluster-scoped resource so a single policy can enable autotls across whole
+/// namespaces via its `namespaceSelector`; when several policies match we pick
+/// the alphabetically first by name so the outcome is deterministic.
+async fn resolve_settings(generator: &Ingress, ctx: &Context<Data>) -> Result<Settings, Error> {
+    let client = ctx.get_ref().client.clone();
+
+    let mut settings = Settings {
+        domain: generator.annotations().get("autotls/domain").cloned(),
+        issuer: generator.annotations().get("autotls/issuer").cloned(),
+    };
+
+    if settings.domain.is_some() && settings.issuer.is_some() {
+        return Ok(settings);
+    }
+
+    let namespace = match generator.namespace() {
+        Some(ns) => ns,
+        None => return Ok(settings),
+    };
+
+    let policies = Api::<AutoTLSPolicy>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| Error::Unexpected(e.into()))?;
+
+    let ingress_labels = generator.labels().clone();
+    let namespace_labels = Api::<Namespace>::all(client)
+        .get(&namespace)
+        .await
+        .map(|n| n.labels().clone())
+        .unwrap_or_default();
+
+    let mut matching = policies
+        .items
+        .iter()
+        .filter(|p| {
+            selector_matches(&p.spec.ingress_selector, &ingress_labels)
+                && selector_matches(&p.spec.namespace_selector, &namespace_labels)
+        })
+        .collect::<Vec<_>>();
+    matching.sort_by_key(|p| p.name());
+
+    if let Some(policy) = matching.first() {
+        settings.domain = settings.domain.or_else(|| policy.spec.domain.clone());
+        settings.issuer = settings.issuer.or_else(|| policy.spec.issuer.clone());
+    }
+
+    Ok(settings)
+}
+
 /// Controller triggers this whenever our main object or our children changed
 async fn reconcile(generator: Arc<Ingress>, ctx: Context<Data>) -> Result<Action, Error> {
     let client = ctx.get_ref().client.clone();
@@ -143,40 +494,345 @@ async fn reconcile(generator: Arc<Ingress>, ctx: Context<Data>) -> Result<Action
         .ok_or(Error::MissingObjectKey(".metadata.namespace"))?;
     trace!("Reconciling ingress {name} on namespace {namespace}");
 
+    if !ingress_class_matches(&generator, &ctx.get_ref().ingress_class) {
+        trace!("Ingress {name} is not in our ingress class, skipping");
+        return Ok(Action::requeue(Duration::from_secs(300)));
+    }
+
     let api = Api::<Ingress>::namespaced(client.clone(), namespace);
+    let settings = resolve_settings(&generator, &ctx).await?;
+    let has_finalizer = generator.finalizers().iter().any(|f| f == FINALIZER);
+
+    if settings.issuer.is_none() {
+        // The kube `finalizer` helper only runs Cleanup on deletion, so an
+        // annotation-driven opt-out (finalizer still present, no issuer) would
+        // otherwise leave our patches and the finalizer behind. Revert here and
+        // drop the finalizer ourselves.
+        if has_finalizer {
+            info!("Ingress {name} opted out of autotls, reverting");
+            cleanup(generator.clone(), ctx.clone()).await?;
+            let remaining = generator
+                .finalizers()
+                .iter()
+                .filter(|f| f.as_str() != FINALIZER)
+                .cloned()
+                .collect::<Vec<_>>();
+            let patch = serde_json::json!({ "metadata": { "finalizers": remaining } });
+            api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await
+                .map_err(Error::IngressCleanupFailed)?;
+            return Ok(Action::await_change());
+        }
 
-    if let Some(domain) = generator.annotations().get("autotls/domain") {
-        let ing = patch_domain(generator.clone(), &domain)?;
+        // Nothing we manage and no finalizer to honor; run the (domain-only)
+        // apply path without stamping a finalizer on objects we never patch.
+        return apply(generator, ctx, &settings).await;
+    }
+
+    finalizer(&api, FINALIZER, generator, |event| async {
+        match event {
+            FinalizerEvent::Apply(ing) => apply(ing, ctx, &settings).await,
+            FinalizerEvent::Cleanup(ing) => cleanup(ing, ctx).await,
+        }
+    })
+    .await
+    .map_err(|e| Error::FinalizerError(Box::new(e)))
+}
+
+/// Applies the autotls patches for an Ingress we own.
+async fn apply(
+    generator: Arc<Ingress>,
+    ctx: Context<Data>,
+    settings: &Settings,
+) -> Result<Action, Error> {
+    let client = ctx.get_ref().client.clone();
+
+    let name = generator
+        .metadata
+        .name
+        .as_ref()
+        .ok_or(Error::MissingObjectKey(".metadata.name"))?;
+    let namespace = generator
+        .metadata
+        .namespace
+        .as_ref()
+        .ok_or(Error::MissingObjectKey(".metadata.namespace"))?;
+
+    let api = Api::<Ingress>::namespaced(client.clone(), namespace);
+    let recorder = ctx.get_ref().recorder(&generator);
+
+    if let Some(domain) = &settings.domain {
+        let ing = patch_domain(generator.clone(), domain)?;
         if let Some(ing) = ing {
             info!("Patching domain for ingress {name}");
-            api.patch(
-                name,
-                &PatchParams::apply("autotls-controller/domain-patcher").force(),
-                &Patch::Apply(&ing),
+            if let Err(e) = api
+                .patch(
+                    name,
+                    &PatchParams::apply("autotls-controller/domain-patcher").force(),
+                    &Patch::Apply(&ing),
+                )
+                .await
+            {
+                publish(
+                    &recorder,
+                    EventType::Warning,
+                    "PatchFailed",
+                    "PatchDomain",
+                    format!("Failed to rewrite hosts: {e}"),
+                )
+                .await;
+                return Err(Error::IngressPatchFailed(e));
+            }
+            publish(
+                &recorder,
+                EventType::Normal,
+                "DomainRewrite",
+                "PatchDomain",
+                format!("Suffixed dotless hosts with domain {domain}"),
             )
-            .await
-            .map_err(Error::IngressPatchFailed)?;
+            .await;
         }
     }
 
-    if let Some(issuer) = generator.annotations().get("autotls/issuer") {
-        let ing = patch_tls(generator.clone(), &issuer)?;
+    if let Some(issuer) = &settings.issuer {
+        let ing = patch_tls(generator.clone(), issuer)?;
         if let Some(ing) = ing {
             info!("Patching tls for ingress {name}");
-            api.patch(
-                name,
-                &PatchParams::apply("autotls-controller/tls-patcher"),
-                &Patch::Apply(&ing),
+            if let Err(e) = api
+                .patch(
+                    name,
+                    &PatchParams::apply("autotls-controller/tls-patcher"),
+                    &Patch::Apply(&ing),
+                )
+                .await
+            {
+                publish(
+                    &recorder,
+                    EventType::Warning,
+                    "PatchFailed",
+                    "PatchTls",
+                    format!("Failed to inject TLS stanza: {e}"),
+                )
+                .await;
+                return Err(Error::IngressPatchFailed(e));
+            }
+            publish(
+                &recorder,
+                EventType::Normal,
+                "TlsConfigured",
+                "PatchTls",
+                format!("Injected TLS stanza for ingress {name} via issuer {issuer}"),
             )
-            .await
-            .map_err(Error::IngressPatchFailed)?;
+            .await;
+        }
+    }
+
+    // Once we've requested TLS, surface whether the certificate was issued by
+    // reading the managed Certificate's Ready condition back onto the Ingress.
+    // While issuance is pending we requeue quickly instead of the flat 300s.
+    let mut requeue = Duration::from_secs(300);
+    // Only the cert-manager path produces a `Certificate` object we can read
+    // readiness from; the `auto` (tls-acme / kube-lego) path has nothing to
+    // watch, so we leave it on the flat requeue instead of hot-looping.
+    if settings.issuer.as_deref().is_some_and(|i| i != "auto") {
+        // A single Ingress may now own several per-domain certificates (named
+        // after the secrets we requested). Aggregate their Ready conditions:
+        // any failure wins, then any pending, otherwise everything is issued.
+        let certs = Api::<Certificate>::namespaced(client.clone(), namespace);
+        let secrets = managed_secret_names(&generator);
+        let mut statuses = Vec::new();
+        for secret in &secrets {
+            let s = match certs.get(secret).await {
+                Ok(cert) => certificate_status(&cert),
+                Err(kube::Error::Api(e)) if e.code == 404 => "Pending",
+                Err(e) => {
+                    publish(
+                        &recorder,
+                        EventType::Warning,
+                        "StatusCheckFailed",
+                        "ProvisionTls",
+                        format!("Failed to read Certificate {secret}: {e}"),
+                    )
+                    .await;
+                    return Err(Error::IngressPatchFailed(e));
+                }
+            };
+            statuses.push(s);
+        }
+        // When the Ingress lists no TLS secret yet, stay on the flat requeue
+        // rather than pinning it to a tight `Pending` loop. A listed secret
+        // whose Certificate does not exist yet counts as `Pending`.
+        let status = if statuses.is_empty() {
+            None
+        } else if statuses.contains(&"Failed") {
+            Some("Failed")
+        } else if statuses.contains(&"Pending") {
+            Some("Pending")
+        } else {
+            Some("Issued")
+        };
+
+        if let Some(status) = status {
+            if generator.annotations().get("autotls/status").map(String::as_str) != Some(status) {
+                info!("Ingress {name} certificate status is {status}");
+                let patch = serde_json::json!({
+                    "metadata": { "annotations": { "autotls/status": status } },
+                });
+                if let Err(e) = api
+                    .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+                    .await
+                {
+                    publish(
+                        &recorder,
+                        EventType::Warning,
+                        "PatchFailed",
+                        "UpdateStatus",
+                        format!("Failed to update autotls/status: {e}"),
+                    )
+                    .await;
+                    return Err(Error::IngressPatchFailed(e));
+                }
+
+                let certificates = secrets.join(", ");
+                match status {
+                    "Issued" => {
+                        publish(
+                            &recorder,
+                            EventType::Normal,
+                            "Issued",
+                            "ProvisionTls",
+                            format!("Certificate(s) {certificates} were issued"),
+                        )
+                        .await;
+                    }
+                    "Failed" => {
+                        publish(
+                            &recorder,
+                            EventType::Warning,
+                            "IssuanceFailed",
+                            "ProvisionTls",
+                            format!("Certificate(s) {certificates} failed to issue"),
+                        )
+                        .await;
+                    }
+                    _ => {}
+                }
+            }
+
+            if status == "Pending" {
+                requeue = Duration::from_secs(15);
+            }
+        }
+    }
+
+    Ok(Action::requeue(requeue))
+}
+
+/// Reverts the patches we applied when an Ingress opts out or is deleted.
+///
+/// We strip the TLS stanza and the annotations we injected with a merge patch
+/// (setting each key to `null` deletes it) and delete the managed `{name}-tls`
+/// Secrets. A failure here propagates out of the finalizer so the transaction
+/// is retried instead of orphaning resources.
+async fn cleanup(generator: Arc<Ingress>, ctx: Context<Data>) -> Result<Action, Error> {
+    let client = ctx.get_ref().client.clone();
+
+    let name = generator
+        .metadata
+        .name
+        .as_ref()
+        .ok_or(Error::MissingObjectKey(".metadata.name"))?;
+    let namespace = generator
+        .metadata
+        .namespace
+        .as_ref()
+        .ok_or(Error::MissingObjectKey(".metadata.namespace"))?;
+
+    info!("Reverting autotls patches for ingress {name}");
+
+    // Capture the secrets we requested before we null out the TLS stanza.
+    let managed = managed_secret_names(&generator);
+
+    let api = Api::<Ingress>::namespaced(client.clone(), namespace);
+    let patch = serde_json::json!({
+        "metadata": {
+            "annotations": {
+                "ingress.kubernetes.io/ssl-redirect": null,
+                "ingress.kubernetes.io/backend-protocol": null,
+                "cert-manager.io/cluster-issuer": null,
+                "kubernetes.io/tls-acme": null,
+                "autotls/status": null,
+            },
+        },
+        "spec": { "tls": null },
+    });
+    api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(Error::IngressCleanupFailed)?;
+
+    // Delete every managed secret we requested, including the per-domain
+    // `{name}-{domain}-tls` variants.
+    let secrets = Api::<Secret>::namespaced(client, namespace);
+    for secret in managed {
+        match secrets.delete(&secret, &DeleteParams::default()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => return Err(Error::IngressCleanupFailed(e)),
+        }
+    }
+
+    Ok(Action::await_change())
+}
+
+/// The policy controller triggers this whenever an [`AutoTLSPolicy`] changes.
+///
+/// A policy has no object of its own to patch; it simply re-drives every
+/// Ingress it selects so the effective settings are recomputed against the
+/// shared [`reconcile`] path. `AutoTLSPolicy` is cluster-scoped, so a single
+/// policy can select Ingresses across any namespace its `namespaceSelector`
+/// matches.
+async fn reconcile_policy(policy: Arc<AutoTLSPolicy>, ctx: Context<Data>) -> Result<Action, Error> {
+    let client = ctx.get_ref().client.clone();
+
+    let ingresses = Api::<Ingress>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+        .map_err(|e| Error::Unexpected(e.into()))?;
+
+    // Cache namespace label lookups so we probe each namespace at most once.
+    let mut namespace_labels = BTreeMap::<String, BTreeMap<String, String>>::new();
+
+    for ing in ingresses {
+        let ns = match ing.namespace() {
+            Some(ns) => ns,
+            None => continue,
+        };
+        if !namespace_labels.contains_key(&ns) {
+            let labels = Api::<Namespace>::all(client.clone())
+                .get(&ns)
+                .await
+                .map(|n| n.labels().clone())
+                .unwrap_or_default();
+            namespace_labels.insert(ns.clone(), labels);
+        }
+
+        if selector_matches(&policy.spec.namespace_selector, &namespace_labels[&ns])
+            && selector_matches(&policy.spec.ingress_selector, &ing.labels())
+        {
+            reconcile(Arc::new(ing), ctx.clone()).await?;
         }
     }
 
     Ok(Action::requeue(Duration::from_secs(300)))
 }
 
-/// The controller triggers this on reconcile errors
+/// The controller triggers this on reconcile errors.
+///
+/// This kube version's `error_policy` doesn't receive the offending object, so
+/// it only logs the error. Warning Events are emitted where `apply` has the
+/// Ingress at hand: failed host rewrite, TLS patch, Certificate read and status
+/// update calls. Other errors (from `patch_domain`/`patch_tls`, missing object
+/// keys, policy resolution, the finalizer and `cleanup`) produce no Event.
 fn error_policy(error: &Error, _ctx: Context<Data>) -> Action {
     error!("{error}");
     Action::requeue(Duration::from_secs(1))
@@ -185,6 +841,47 @@ fn error_policy(error: &Error, _ctx: Context<Data>) -> Action {
 // Data we want access to in error/reconcile calls
 struct Data {
     client: Client,
+    ingress_class: Option<String>,
+    reporter: Reporter,
+}
+
+impl Data {
+    /// Builds an event recorder scoped to the given Ingress so outcomes show
+    /// up under `kubectl describe ingress`.
+    fn recorder(&self, obj: &Ingress) -> Recorder {
+        Recorder::new(self.client.clone(), self.reporter.clone(), obj.object_ref(&()))
+    }
+}
+
+/// Publishes an event, downgrading a publish failure to a log line so event
+/// plumbing never masks the actual reconcile outcome.
+async fn publish(recorder: &Recorder, type_: EventType, reason: &str, action: &str, note: String) {
+    let event = Event {
+        type_,
+        reason: reason.to_owned(),
+        note: Some(note),
+        action: action.to_owned(),
+        secondary: None,
+    };
+    if let Err(e) = recorder.publish(event).await {
+        warn!("Failed to publish event: {e}");
+    }
+}
+
+/// Resolves the ingress class to reconcile from the `--ingress-class` flag,
+/// falling back to the `INGRESS_CLASS` environment variable. When neither is
+/// set we reconcile every Ingress in the cluster.
+fn ingress_class_option() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--ingress-class=") {
+            return Some(value.to_owned());
+        }
+        if arg == "--ingress-class" {
+            return args.next();
+        }
+    }
+    std::env::var("INGRESS_CLASS").ok()
 }
 
 #[tokio::main]
@@ -195,15 +892,271 @@ async fn main() -> Result<()> {
     let client = Client::try_default().await?;
     let ingresses: Api<Ingress> = Api::all(client.clone());
 
-    let context = Context::new(Data { client });
-    Controller::new(ingresses, ListParams::default())
-        .run(reconcile, error_policy, context)
+    let ingress_class = ingress_class_option();
+    if let Some(class) = &ingress_class {
+        info!("Scoping reconciliation to ingress class {class}");
+    }
+
+    let certificates: Api<Certificate> = Api::all(client.clone());
+    let secrets: Api<Secret> = Api::all(client.clone());
+    let policies: Api<AutoTLSPolicy> = Api::all(client.clone());
+
+    let reporter = Reporter {
+        controller: "autotls-controller".into(),
+        instance: std::env::var("POD_NAME").ok(),
+    };
+
+    let context = Context::new(Data {
+        client,
+        ingress_class,
+        reporter,
+    });
+
+    let ingress_controller = Controller::new(ingresses, ListParams::default())
+        .watches(certificates, ListParams::default(), |cert| {
+            parent_ingress(&cert).into_iter()
+        })
+        .watches(secrets, ListParams::default(), |secret| {
+            parent_ingress(&secret).into_iter()
+        })
+        .run(reconcile, error_policy, context.clone())
         .for_each(|res| async move {
             match res {
                 Ok((o, _)) => info!("Reconciled ingress {}", o.name),
                 Err(e) => warn!("Reconcile failed: {e}"),
             }
-        })
-        .await;
+        });
+
+    let policy_controller = Controller::new(policies, ListParams::default())
+        .run(reconcile_policy, error_policy, context)
+        .for_each(|res| async move {
+            match res {
+                Ok((o, _)) => info!("Reconciled policy {}", o.name),
+                Err(e) => warn!("Policy reconcile failed: {e}"),
+            }
+        });
+
+    tokio::join!(ingress_controller, policy_controller);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::networking::v1::IngressRule;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelectorRequirement;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn ingress(name: &str, hosts: &[&str]) -> Ingress {
+        Ingress {
+            metadata: ObjectMeta {
+                name: Some(name.to_owned()),
+                ..ObjectMeta::default()
+            },
+            spec: Some(IngressSpec {
+                rules: Some(
+                    hosts
+                        .iter()
+                        .map(|h| IngressRule {
+                            host: Some((*h).to_owned()),
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parent_domain_strips_only_subdomains() {
+        let cases = [
+            ("foo.example.com", "example.com"),
+            ("a.b.c.d", "b.c.d"),
+            ("example.com", "example.com"),
+            ("web", "web"),
+        ];
+        for (host, want) in cases {
+            assert_eq!(parent_domain(host), want, "host {host}");
+        }
+    }
+
+    #[test]
+    fn domain_slug_replaces_dots() {
+        assert_eq!(domain_slug("example.com"), "example-com");
+        assert_eq!(domain_slug("a.b.c"), "a-b-c");
+    }
+
+    #[test]
+    fn certificate_status_reads_ready_condition() {
+        let cases = [("True", "Issued"), ("False", "Failed"), ("Unknown", "Pending")];
+        for (status, want) in cases {
+            let mut cert = Certificate::new(
+                "web-tls",
+                CertificateSpec {
+                    secret_name: "web-tls".to_owned(),
+                    dns_names: vec![],
+                },
+            );
+            cert.status = Some(CertificateStatus {
+                conditions: vec![CertificateCondition {
+                    type_: "Ready".to_owned(),
+                    status: status.to_owned(),
+                    reason: None,
+                }],
+            });
+            assert_eq!(certificate_status(&cert), want, "status {status}");
+        }
+
+        let bare = Certificate::new(
+            "web-tls",
+            CertificateSpec {
+                secret_name: "web-tls".to_owned(),
+                dns_names: vec![],
+            },
+        );
+        assert_eq!(certificate_status(&bare), "Pending");
+    }
+
+    #[test]
+    fn ingress_class_matches_field_annotation_and_default() {
+        // No configured class: everything matches.
+        assert!(ingress_class_matches(&ingress("web", &["web"]), &None));
+
+        // Matches via spec.ingressClassName.
+        let mut via_field = ingress("web", &["web"]);
+        via_field.spec.as_mut().unwrap().ingress_class_name = Some("nginx".to_owned());
+        assert!(ingress_class_matches(&via_field, &Some("nginx".to_owned())));
+
+        // Matches via the legacy annotation.
+        let mut via_annotation = ingress("web", &["web"]);
+        via_annotation.metadata.annotations =
+            Some(labels(&[("kubernetes.io/ingress.class", "nginx")]));
+        assert!(ingress_class_matches(&via_annotation, &Some("nginx".to_owned())));
+
+        // A different class does not match.
+        assert!(!ingress_class_matches(&via_field, &Some("traefik".to_owned())));
+    }
+
+    #[test]
+    fn selector_matches_labels_and_expressions() {
+        let subject = labels(&[("team", "infra"), ("tier", "edge")]);
+
+        // Missing selector matches everything.
+        assert!(selector_matches(&None, &subject));
+
+        // matchLabels subset.
+        let ml = LabelSelector {
+            match_labels: Some(labels(&[("team", "infra")])),
+            ..Default::default()
+        };
+        assert!(selector_matches(&Some(ml), &subject));
+
+        let ml_miss = LabelSelector {
+            match_labels: Some(labels(&[("team", "apps")])),
+            ..Default::default()
+        };
+        assert!(!selector_matches(&Some(ml_miss), &subject));
+
+        // matchExpressions operators.
+        let expr = |op: &str, values: Option<Vec<String>>| LabelSelector {
+            match_expressions: Some(vec![LabelSelectorRequirement {
+                key: "team".to_owned(),
+                operator: op.to_owned(),
+                values,
+            }]),
+            ..Default::default()
+        };
+        assert!(selector_matches(
+            &Some(expr("In", Some(vec!["infra".into(), "apps".into()]))),
+            &subject
+        ));
+        assert!(!selector_matches(
+            &Some(expr("NotIn", Some(vec!["infra".into()]))),
+            &subject
+        ));
+        assert!(selector_matches(&Some(expr("Exists", None)), &subject));
+        assert!(!selector_matches(&Some(expr("DoesNotExist", None)), &subject));
+    }
+
+    #[test]
+    fn patch_domain_expands_comma_separated_domains() {
+        let ing = patch_domain(Arc::new(ingress("web", &["web", "api.example.com"])), "a.com,b.com")
+            .unwrap()
+            .expect("expected a patch");
+        let hosts = ing
+            .spec
+            .unwrap()
+            .rules
+            .unwrap()
+            .into_iter()
+            .filter_map(|r| r.host)
+            .collect::<Vec<_>>();
+        // Dotless `web` expands per domain; the dotted host is left untouched.
+        assert_eq!(hosts, vec!["web.a.com", "web.b.com", "api.example.com"]);
+    }
+
+    #[test]
+    fn patch_tls_groups_hosts_by_parent_domain() {
+        let ing = patch_tls(
+            Arc::new(ingress("web", &["a.example.com", "b.example.com", "c.other.com"])),
+            "letsencrypt",
+        )
+        .unwrap()
+        .expect("expected a patch");
+        let tls = ing.spec.unwrap().tls.unwrap();
+        let mut entries = tls
+            .into_iter()
+            .map(|t| (t.secret_name.unwrap(), t.hosts.unwrap()))
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "web-example-com-tls".to_owned(),
+                    vec!["a.example.com".to_owned(), "b.example.com".to_owned()]
+                ),
+                ("web-other-com-tls".to_owned(), vec!["c.other.com".to_owned()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn patch_tls_single_group_keeps_plain_secret_name() {
+        let ing = patch_tls(Arc::new(ingress("web", &["a.example.com"])), "letsencrypt")
+            .unwrap()
+            .expect("expected a patch");
+        let tls = ing.spec.unwrap().tls.unwrap();
+        assert_eq!(tls.len(), 1);
+        assert_eq!(tls[0].secret_name.as_deref(), Some("web-tls"));
+    }
+
+    #[test]
+    fn patch_tls_wildcard_keeps_apex_san() {
+        let mut source = ingress("web", &["example.com", "a.example.com"]);
+        source.metadata.annotations = Some(labels(&[("autotls/wildcard", "true")]));
+        let ing = patch_tls(Arc::new(source), "letsencrypt")
+            .unwrap()
+            .expect("expected a patch");
+        let tls = ing.spec.unwrap().tls.unwrap();
+        assert_eq!(tls.len(), 1);
+        let mut hosts = tls[0].hosts.clone().unwrap();
+        hosts.sort();
+        assert_eq!(hosts, vec!["*.example.com".to_owned(), "example.com".to_owned()]);
+    }
+
+    #[test]
+    fn patch_tls_rejects_invalid_ssl_redirect() {
+        let mut source = ingress("web", &["a.example.com"]);
+        source.metadata.annotations = Some(labels(&[("autotls/ssl-redirect", "yes")]));
+        assert!(patch_tls(Arc::new(source), "letsencrypt").unwrap().is_none());
+    }
+}